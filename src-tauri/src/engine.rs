@@ -0,0 +1,141 @@
+//! Resolves and invokes the `winstyles` Python engine, preferring the bundled
+//! Tauri sidecar binary and falling back to a PATH-based `python` interpreter
+//! for local development.
+
+use std::fs;
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use crate::error::EngineError;
+
+/// Name the sidecar binary is registered under in `tauri.conf.json`. Tauri
+/// resolves the platform-specific `winstyles-<target-triple>[.exe]` file in
+/// the bundle's resource directory for us.
+const SIDECAR_NAME: &str = "winstyles";
+
+/// Environment variables forwarded to the engine process unconditionally,
+/// beyond the `PYTHONUTF8`/`PYTHONIOENCODING` pair it always needs. Covers
+/// what a typical Python interpreter needs to locate itself and format
+/// output correctly on a given OS/locale. `PATH` has to stay in here: the
+/// dev/debug fallback resolves a bare `python` through the OS loader, which
+/// requires it.
+const BASE_ALLOWED_VARS: &[&str] = &[
+    "PATH",
+    "SYSTEMROOT",
+    "TEMP",
+    "TMP",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+];
+
+/// Config-supplied extension to [`BASE_ALLOWED_VARS`], loaded once at startup
+/// from `<app_config_dir>/env.json` and kept in managed state.
+#[derive(Clone, Default, Deserialize)]
+pub struct EnvConfig {
+    #[serde(default)]
+    pub extra_allow: Vec<String>,
+}
+
+impl EnvConfig {
+    pub fn load(app: &AppHandle) -> EnvConfig {
+        let Ok(config_dir) = app.path().app_config_dir() else {
+            return EnvConfig::default();
+        };
+
+        fs::read_to_string(config_dir.join("env.json"))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// Interpreter used when no sidecar is bundled (dev builds, or a sidecar
+/// binary missing from the resource directory). Override with the `PYTHON`
+/// env var to point at a specific interpreter.
+fn python_cmd() -> String {
+    std::env::var("PYTHON").unwrap_or_else(|_| "python".to_string())
+}
+
+/// Clears the inherited environment and repopulates only the allowed
+/// variables (base set plus the config-supplied extra allowlist) so the
+/// engine can't be influenced by unrelated vars like `PYTHONPATH` leaking in
+/// from the parent process.
+fn sanitize_env(
+    command: tauri_plugin_shell::process::Command,
+    extra_allow: &[String],
+) -> tauri_plugin_shell::process::Command {
+    let mut command = command.env_clear();
+
+    for name in BASE_ALLOWED_VARS
+        .iter()
+        .copied()
+        .chain(extra_allow.iter().map(String::as_str))
+    {
+        if let Ok(value) = std::env::var(name) {
+            command = command.env(name, value);
+        }
+    }
+
+    command
+        .env("PYTHONUTF8", "1")
+        .env("PYTHONIOENCODING", "utf-8")
+}
+
+/// Builds the shell command to run, trying the bundled sidecar first and
+/// falling back to `python -m winstyles` on PATH if the sidecar isn't
+/// available in this build. Shared by the blocking and streaming call paths.
+pub(crate) fn build_command(
+    app: &AppHandle,
+    args: &[String],
+) -> Result<tauri_plugin_shell::process::Command, String> {
+    let shell = app.shell();
+
+    let command = if let Ok(sidecar) = shell.sidecar(SIDECAR_NAME) {
+        sidecar.args(args)
+    } else {
+        let mut python_args = vec!["-m".to_string(), "winstyles".to_string()];
+        python_args.extend_from_slice(args);
+        shell.command(python_cmd()).args(python_args)
+    };
+
+    let extra_allow = app
+        .try_state::<EnvConfig>()
+        .map(|config| config.extra_allow.clone())
+        .unwrap_or_default();
+
+    Ok(sanitize_env(command, &extra_allow))
+}
+
+/// Runs the engine with `args` (e.g. `["scan", "-f", "json"]`) and returns its
+/// stdout on success, or a typed error on a non-zero exit. Blocks until the
+/// process exits; for long-running jobs where the caller wants progress as
+/// it happens, see [`crate::jobs`].
+pub async fn run_command(app: &AppHandle, args: &[String]) -> Result<String, EngineError> {
+    let output = build_command(app, args)
+        .map_err(EngineError::PythonNotFound)?
+        .output()
+        .await
+        .map_err(|err| EngineError::PythonNotFound(err.to_string()))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(EngineError::NonZeroExit(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ))
+    }
+}
+
+/// Runs the engine like [`run_command`] and deserializes its stdout as JSON
+/// into `T`.
+pub async fn run_json<T: DeserializeOwned>(
+    app: &AppHandle,
+    args: &[String],
+) -> Result<T, EngineError> {
+    let raw = run_command(app, args).await?;
+    serde_json::from_str(&raw).map_err(|err| EngineError::MalformedJson(err.to_string()))
+}
@@ -0,0 +1,36 @@
+//! Typed error for engine invocations, serialized to the frontend as a
+//! tagged object instead of an opaque string so the UI can branch on `kind`.
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "detail")]
+pub enum EngineError {
+    /// Neither the bundled sidecar nor a PATH `python` interpreter could be
+    /// resolved or spawned.
+    PythonNotFound(String),
+    /// The engine ran and exited non-zero; `detail` is its stderr.
+    NonZeroExit(String),
+    /// A caller-supplied path is invalid (e.g. empty) or failed the active
+    /// [`crate::scope::Scope`] check.
+    OutOfScope(String),
+    /// The engine's stdout wasn't the JSON shape we expected.
+    MalformedJson(String),
+    /// A caller-supplied id didn't match anything we track, e.g. an unknown
+    /// restore point id passed to `rollback`.
+    NotFound(String),
+}
+
+impl std::fmt::Display for EngineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineError::PythonNotFound(detail) => write!(f, "python not found: {detail}"),
+            EngineError::NonZeroExit(detail) => write!(f, "engine exited with an error: {detail}"),
+            EngineError::OutOfScope(detail) => write!(f, "invalid or out-of-scope path: {detail}"),
+            EngineError::MalformedJson(detail) => write!(f, "malformed engine output: {detail}"),
+            EngineError::NotFound(detail) => write!(f, "not found: {detail}"),
+        }
+    }
+}
+
+impl std::error::Error for EngineError {}
@@ -0,0 +1,144 @@
+//! Long-running engine invocations (`scan`, `import`) that stream progress to
+//! the frontend instead of blocking until the process exits.
+//!
+//! The frontend gets a job id back immediately, listens for
+//! `winstyles://progress` events as stdout/stderr lines arrive, and
+//! `winstyles://complete` once the process exits. [`cancel_job`] kills the
+//! underlying child if the user wants to interrupt it.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+
+use crate::engine;
+
+/// Tracks the child processes behind in-flight jobs so they can be cancelled.
+#[derive(Default)]
+pub struct JobRegistry {
+    next_id: AtomicU64,
+    children: Mutex<HashMap<String, CommandChild>>,
+}
+
+#[derive(Clone, Serialize)]
+struct ProgressEvent<'a> {
+    job_id: &'a str,
+    stream: &'a str,
+    line: String,
+}
+
+#[derive(Clone, Serialize)]
+struct CompleteEvent<'a> {
+    job_id: &'a str,
+    success: bool,
+    message: String,
+}
+
+/// Runs after a job's process exits, given the full stdout it produced and
+/// whether it exited successfully. Lets callers that care about the result
+/// (e.g. recording a restore point for a completed import) hook completion
+/// without `spawn_job` itself knowing anything about what it ran.
+pub type CompletionHook = Box<dyn FnOnce(&AppHandle, bool, &str) + Send>;
+
+/// Spawns `args` on the engine and streams its output as `winstyles://progress`
+/// events under a freshly allocated job id, returning that id without waiting
+/// for the process to finish. `on_complete`, if given, runs once the process
+/// exits with the job's accumulated stdout.
+pub fn spawn_job(
+    app: &AppHandle,
+    registry: &State<'_, JobRegistry>,
+    args: &[String],
+    on_complete: Option<CompletionHook>,
+) -> Result<String, String> {
+    let command = engine::build_command(app, args)?;
+    let (mut events, child) = command.spawn().map_err(|err| err.to_string())?;
+
+    let job_id = registry.next_id.fetch_add(1, Ordering::SeqCst).to_string();
+    registry
+        .children
+        .lock()
+        .unwrap()
+        .insert(job_id.clone(), child);
+
+    let task_app = app.clone();
+    let task_job_id = job_id.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut success = true;
+        let mut stdout_buf = String::new();
+        let mut stderr_buf = String::new();
+
+        while let Some(event) = events.recv().await {
+            match event {
+                CommandEvent::Stdout(bytes) => {
+                    let line = String::from_utf8_lossy(&bytes).to_string();
+                    stdout_buf.push_str(&line);
+                    stdout_buf.push('\n');
+                    let _ = task_app.emit(
+                        "winstyles://progress",
+                        ProgressEvent {
+                            job_id: &task_job_id,
+                            stream: "stdout",
+                            line,
+                        },
+                    );
+                }
+                CommandEvent::Stderr(bytes) => {
+                    let line = String::from_utf8_lossy(&bytes).to_string();
+                    stderr_buf.push_str(&line);
+                    stderr_buf.push('\n');
+                    let _ = task_app.emit(
+                        "winstyles://progress",
+                        ProgressEvent {
+                            job_id: &task_job_id,
+                            stream: "stderr",
+                            line,
+                        },
+                    );
+                }
+                CommandEvent::Terminated(payload) => {
+                    success = payload.code.unwrap_or(1) == 0;
+                }
+                CommandEvent::Error(err) => {
+                    success = false;
+                    stderr_buf.push_str(&err);
+                    stderr_buf.push('\n');
+                }
+                _ => {}
+            }
+        }
+
+        let _ = task_app.emit(
+            "winstyles://complete",
+            CompleteEvent {
+                job_id: &task_job_id,
+                success,
+                message: if success { String::new() } else { stderr_buf },
+            },
+        );
+
+        if let Some(on_complete) = on_complete {
+            on_complete(&task_app, success, &stdout_buf);
+        }
+
+        task_app
+            .state::<JobRegistry>()
+            .children
+            .lock()
+            .unwrap()
+            .remove(&task_job_id);
+    });
+
+    Ok(job_id)
+}
+
+/// Kills the child process backing `job_id`, if it's still running.
+#[tauri::command]
+pub fn cancel_job(registry: State<'_, JobRegistry>, job_id: String) -> Result<(), String> {
+    match registry.children.lock().unwrap().remove(&job_id) {
+        Some(child) => child.kill().map_err(|err| err.to_string()),
+        None => Err(format!("no running job with id {job_id}")),
+    }
+}
@@ -1,26 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod engine;
+mod error;
+mod jobs;
+mod models;
+mod restore;
+mod scope;
+
 use std::process::Command;
+use tauri::{AppHandle, Emitter, Manager, State};
 
-fn python_cmd() -> String {
-    std::env::var("PYTHON").unwrap_or_else(|_| "python".to_string())
-}
-
-fn run_command(args: &[String]) -> Result<String, String> {
-    let output = Command::new(python_cmd())
-        .args(args)
-        .env("PYTHONUTF8", "1")
-        .env("PYTHONIOENCODING", "utf-8")
-        .output()
-        .map_err(|err| err.to_string())?;
-
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        Err(stderr)
-    }
-}
+use error::EngineError;
+use jobs::JobRegistry;
+use models::{DiffResult, ExportSummary, ImportOutcome, InspectDocument, ScanReport};
+use restore::RestoreRegistry;
+use scope::{Access, Scope};
 
 #[tauri::command]
 fn open_output_folder() -> Result<(), String> {
@@ -37,17 +31,8 @@ fn open_output_folder() -> Result<(), String> {
     }
 }
 
-#[tauri::command]
-fn scan(
-    categories: Option<Vec<String>>,
-    format: Option<String>,
-    modified_only: Option<bool>,
-) -> Result<String, String> {
-    let mut cmd = vec![
-        "-m".to_string(),
-        "winstyles".to_string(),
-        "scan".to_string(),
-    ];
+fn scan_args(categories: Option<Vec<String>>, modified_only: Option<bool>) -> Vec<String> {
+    let mut cmd = vec!["scan".to_string(), "-f".to_string(), "json".to_string()];
 
     if let Some(categories) = categories {
         for category in categories {
@@ -56,34 +41,50 @@ fn scan(
         }
     }
 
-    if let Some(format) = format {
-        cmd.push("-f".to_string());
-        cmd.push(format);
-    }
-
     if modified_only.unwrap_or(false) {
         cmd.push("--modified-only".to_string());
     }
 
-    run_command(&cmd)
+    cmd
 }
 
 #[tauri::command]
-fn export_config(
+async fn scan(
+    app: AppHandle,
+    categories: Option<Vec<String>>,
+    modified_only: Option<bool>,
+) -> Result<ScanReport, EngineError> {
+    let cmd = scan_args(categories, modified_only);
+    engine::run_json(&app, &cmd).await
+}
+
+/// Kicks off `scan` as a streaming job instead of waiting for it to finish;
+/// progress arrives via `winstyles://progress` events for the returned job id.
+#[tauri::command]
+fn scan_async(
+    app: AppHandle,
+    registry: State<'_, JobRegistry>,
+    categories: Option<Vec<String>>,
+    modified_only: Option<bool>,
+) -> Result<String, String> {
+    let cmd = scan_args(categories, modified_only);
+    jobs::spawn_job(&app, &registry, &cmd, None)
+}
+
+#[tauri::command]
+async fn export_config(
+    app: AppHandle,
+    scope: State<'_, Scope>,
     path: String,
     categories: Option<String>,
     include_defaults: Option<bool>,
-) -> Result<String, String> {
+) -> Result<ExportSummary, EngineError> {
     if path.trim().is_empty() {
-        return Err("path is required".to_string());
+        return Err(EngineError::OutOfScope("path is required".to_string()));
     }
+    scope.check(&path, Access::Write)?;
 
-    let mut cmd = vec![
-        "-m".to_string(),
-        "winstyles".to_string(),
-        "export".to_string(),
-        path,
-    ];
+    let mut cmd = vec!["export".to_string(), path, "-f".to_string(), "json".to_string()];
 
     if let Some(categories) = categories {
         for category in categories.split(',').map(|c| c.trim()).filter(|c| !c.is_empty()) {
@@ -96,56 +97,126 @@ fn export_config(
         cmd.push("--include-defaults".to_string());
     }
 
-    run_command(&cmd)
+    engine::run_json(&app, &cmd).await
+}
+
+fn import_args(path: String, dry_run: Option<bool>, skip_restore: Option<bool>) -> Vec<String> {
+    let mut cmd = vec!["import".to_string(), path, "-f".to_string(), "json".to_string()];
+
+    if dry_run.unwrap_or(false) {
+        cmd.push("--dry-run".to_string());
+    }
+
+    if skip_restore.unwrap_or(false) {
+        cmd.push("--skip-restore-point".to_string());
+    }
+
+    cmd
 }
 
 #[tauri::command]
-fn import_config(
+async fn import_config(
+    app: AppHandle,
+    scope: State<'_, Scope>,
+    restore_points: State<'_, RestoreRegistry>,
     path: String,
     dry_run: Option<bool>,
     skip_restore: Option<bool>,
-) -> Result<String, String> {
+) -> Result<ImportOutcome, EngineError> {
     if path.trim().is_empty() {
-        return Err("path is required".to_string());
+        return Err(EngineError::OutOfScope("path is required".to_string()));
     }
+    scope.check(&path, Access::Read)?;
 
-    let mut cmd = vec![
-        "-m".to_string(),
-        "winstyles".to_string(),
-        "import".to_string(),
-        path,
-    ];
+    let is_dry_run = dry_run.unwrap_or(false);
+    let skip_restore_point = skip_restore.unwrap_or(false);
+    let cmd = import_args(path.clone(), dry_run, skip_restore);
+    let outcome = engine::run_json(&app, &cmd).await?;
 
-    if dry_run.unwrap_or(false) {
-        cmd.push("--dry-run".to_string());
+    if !is_dry_run && !skip_restore_point {
+        if let ImportOutcome::Result(result) = &outcome {
+            restore_points.record_for_import(&path, result);
+        }
     }
 
-    if skip_restore.unwrap_or(false) {
-        cmd.push("--skip-restore-point".to_string());
+    Ok(outcome)
+}
+
+/// Kicks off `import` as a streaming job instead of waiting for it to finish;
+/// progress arrives via `winstyles://progress` events for the returned job id.
+/// On a successful, non-dry-run, non-skipped import, records a restore point
+/// from the job's output just like the synchronous [`import_config`] does.
+#[tauri::command]
+fn import_config_async(
+    app: AppHandle,
+    registry: State<'_, JobRegistry>,
+    scope: State<'_, Scope>,
+    path: String,
+    dry_run: Option<bool>,
+    skip_restore: Option<bool>,
+) -> Result<String, String> {
+    if path.trim().is_empty() {
+        return Err("path is required".to_string());
     }
+    scope.check(&path, Access::Read).map_err(|err| err.to_string())?;
 
-    run_command(&cmd)
+    let is_dry_run = dry_run.unwrap_or(false);
+    let skip_restore_point = skip_restore.unwrap_or(false);
+    let cmd = import_args(path.clone(), dry_run, skip_restore);
+
+    let on_complete: Option<jobs::CompletionHook> = if is_dry_run || skip_restore_point {
+        None
+    } else {
+        Some(Box::new(move |app: &AppHandle, success, stdout| {
+            if !success {
+                return;
+            }
+
+            let Ok(ImportOutcome::Result(result)) = serde_json::from_str::<ImportOutcome>(stdout)
+            else {
+                eprintln!(
+                    "winstyles: failed to parse import output for '{path}', no restore point recorded"
+                );
+                let _ = app.emit(
+                    "winstyles://restore-point-error",
+                    format!("failed to record a restore point for import of '{path}'"),
+                );
+                return;
+            };
+
+            if let Some(restore_points) = app.try_state::<RestoreRegistry>() {
+                restore_points.record_for_import(&path, &result);
+            }
+        }))
+    };
+
+    jobs::spawn_job(&app, &registry, &cmd, on_complete)
 }
 
 #[tauri::command]
-fn inspect(path: String) -> Result<String, String> {
-    let cmd = vec![
-        "-m".to_string(),
-        "winstyles".to_string(),
-        "inspect".to_string(),
-        path,
-        "-f".to_string(),
-        "json".to_string(),
-    ];
+async fn inspect(
+    app: AppHandle,
+    scope: State<'_, Scope>,
+    path: String,
+) -> Result<InspectDocument, EngineError> {
+    scope.check(&path, Access::Read)?;
+    let cmd = vec!["inspect".to_string(), path, "-f".to_string(), "json".to_string()];
 
-    run_command(&cmd)
+    engine::run_json(&app, &cmd).await
 }
 
 #[tauri::command]
-fn diff(path_a: String, path_b: String, show_all: Option<bool>) -> Result<String, String> {
+async fn diff(
+    app: AppHandle,
+    scope: State<'_, Scope>,
+    path_a: String,
+    path_b: String,
+    show_all: Option<bool>,
+) -> Result<DiffResult, EngineError> {
+    scope.check(&path_a, Access::Read)?;
+    scope.check(&path_b, Access::Read)?;
+
     let mut cmd = vec![
-        "-m".to_string(),
-        "winstyles".to_string(),
         "diff".to_string(),
         path_a,
         path_b,
@@ -157,18 +228,31 @@ fn diff(path_a: String, path_b: String, show_all: Option<bool>) -> Result<String
         cmd.push("--all".to_string());
     }
 
-    run_command(&cmd)
+    engine::run_json(&app, &cmd).await
 }
 
 fn main() {
     tauri::Builder::default()
+        .manage(JobRegistry::default())
+        .setup(|app| {
+            app.manage(Scope::load(&app.handle().clone()));
+            app.manage(engine::EnvConfig::load(&app.handle().clone()));
+            app.manage(RestoreRegistry::load(&app.handle().clone()));
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             scan,
+            scan_async,
             export_config,
             import_config,
+            import_config_async,
             inspect,
             diff,
-            open_output_folder
+            open_output_folder,
+            jobs::cancel_job,
+            scope::get_scope,
+            restore::list_restore_points,
+            restore::rollback,
         ])
         .plugin(tauri_plugin_shell::init())
         .run(tauri::generate_context!())
@@ -0,0 +1,70 @@
+//! Typed shapes for the JSON each engine subcommand prints, so the frontend
+//! gets generated, checkable types instead of re-parsing hand-rolled blobs.
+//!
+//! Each struct keeps the fields we know the engine always sends and flattens
+//! the rest into `extra`, so an engine upgrade that adds a field doesn't
+//! require a matching Rust change before it can round-trip to the UI.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScanReport {
+    pub categories: Vec<String>,
+    pub modified: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ExportSummary {
+    pub path: String,
+    pub categories: Vec<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportPlan {
+    pub path: String,
+    pub dry_run: bool,
+    pub changes: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ImportResult {
+    pub path: String,
+    pub applied: bool,
+    pub restore_point: Option<String>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+/// `import_config` returns a plan for `--dry-run` and a result otherwise;
+/// both are JSON objects the engine prints in the same place, so we try each
+/// shape in turn.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ImportOutcome {
+    Plan(ImportPlan),
+    Result(ImportResult),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InspectDocument {
+    pub path: String,
+    pub entries: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DiffResult {
+    pub path_a: String,
+    pub path_b: String,
+    pub differences: Vec<Value>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
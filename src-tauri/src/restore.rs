@@ -0,0 +1,177 @@
+//! Restore-point registry recording each applied import so it can be listed
+//! and rolled back later, turning imports into auditable, reversible
+//! transactions.
+
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+
+use crate::engine;
+use crate::error::EngineError;
+use crate::models::ImportResult;
+use crate::scope::{Access, Scope};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RestorePoint {
+    pub id: String,
+    pub timestamp: u64,
+    pub source_hash: String,
+    pub categories: Vec<String>,
+    pub backup_location: String,
+}
+
+/// Persisted list of restore points, stored as
+/// `<app_data_dir>/restore_points.json` and loaded once at startup.
+pub struct RestoreRegistry {
+    path: PathBuf,
+    next_id: AtomicU64,
+    entries: Mutex<Vec<RestorePoint>>,
+}
+
+impl RestoreRegistry {
+    pub fn load(app: &AppHandle) -> RestoreRegistry {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .unwrap_or_else(|_| PathBuf::from("."));
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("restore_points.json");
+
+        let entries: Vec<RestorePoint> = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        let next_id = entries
+            .iter()
+            .filter_map(|point| point.id.strip_prefix("rp-"))
+            .filter_map(|suffix| suffix.parse::<u64>().ok())
+            .max()
+            .map(|max| max + 1)
+            .unwrap_or(0);
+
+        RestoreRegistry {
+            path,
+            next_id: AtomicU64::new(next_id),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    fn persist(&self, entries: &[RestorePoint]) {
+        let json = match serde_json::to_string_pretty(entries) {
+            Ok(json) => json,
+            Err(err) => {
+                eprintln!(
+                    "winstyles: failed to serialize restore point registry: {err}"
+                );
+                return;
+            }
+        };
+
+        if let Err(err) = fs::write(&self.path, json) {
+            eprintln!(
+                "winstyles: failed to persist restore point registry to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    /// Records a restore point for a completed, non-dry-run import.
+    pub fn record_for_import(&self, source_path: &str, result: &ImportResult) {
+        let Some(backup_location) = result.restore_point.clone() else {
+            return;
+        };
+
+        let categories = result
+            .extra
+            .get("categories")
+            .and_then(|value| value.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let id = format!("rp-{}", self.next_id.fetch_add(1, Ordering::SeqCst));
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.push(RestorePoint {
+            id,
+            timestamp,
+            source_hash: hash_file(source_path),
+            categories,
+            backup_location,
+        });
+        self.persist(&entries);
+    }
+
+    /// Returns recorded restore points, most recently created first.
+    pub fn list(&self) -> Vec<RestorePoint> {
+        let mut entries = self.entries.lock().unwrap().clone();
+        entries.reverse();
+        entries
+    }
+
+    pub fn find(&self, id: &str) -> Option<RestorePoint> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|point| point.id == id)
+            .cloned()
+    }
+}
+
+/// Non-cryptographic content fingerprint for the imported source file, just
+/// enough to tell a restore point's source apart from a different import.
+fn hash_file(path: &str) -> String {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    if let Ok(bytes) = fs::read(path) {
+        bytes.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Lists all recorded restore points, most recent import history first.
+#[tauri::command]
+pub fn list_restore_points(registry: State<'_, RestoreRegistry>) -> Vec<RestorePoint> {
+    registry.list()
+}
+
+/// Re-invokes the engine to revert the import behind `id` using its recorded
+/// backup location.
+#[tauri::command]
+pub async fn rollback(
+    app: AppHandle,
+    registry: State<'_, RestoreRegistry>,
+    scope: State<'_, Scope>,
+    id: String,
+) -> Result<ImportResult, EngineError> {
+    let point = registry
+        .find(&id)
+        .ok_or_else(|| EngineError::NotFound(format!("no restore point with id '{id}'")))?;
+
+    scope.check(&point.backup_location, Access::Write)?;
+
+    let cmd = vec![
+        "import".to_string(),
+        "--rollback".to_string(),
+        point.backup_location,
+        "-f".to_string(),
+        "json".to_string(),
+    ];
+
+    engine::run_json(&app, &cmd).await
+}
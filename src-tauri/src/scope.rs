@@ -0,0 +1,175 @@
+//! Capability scope restricting which filesystem paths commands may read
+//! from or write to, modeled on Tauri's own ACL/scope mechanism.
+
+use std::fs;
+
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::error::EngineError;
+
+/// Direction a path is being accessed in, used to pick which pattern list to
+/// check it against.
+#[derive(Clone, Copy)]
+pub enum Access {
+    Read,
+    Write,
+}
+
+/// Allow-listed glob patterns for paths commands may touch. `deny` takes
+/// precedence over both allow lists when a path matches more than one.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Scope {
+    pub read_allow: Vec<String>,
+    pub write_allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+}
+
+impl Scope {
+    /// Loads `<app_config_dir>/scope.json` if present, otherwise falls back
+    /// to a default scope covering the user's home directory.
+    pub fn load(app: &AppHandle) -> Scope {
+        if let Ok(config_dir) = app.path().app_config_dir() {
+            let candidate = config_dir.join("scope.json");
+            if let Ok(contents) = fs::read_to_string(candidate) {
+                if let Ok(scope) = serde_json::from_str::<Scope>(&contents) {
+                    return scope;
+                }
+            }
+        }
+
+        let allow = match app.path().home_dir() {
+            Ok(dir) => vec![format!("{}/**", dir.to_string_lossy())],
+            Err(_) => Vec::new(),
+        };
+
+        Scope {
+            read_allow: allow.clone(),
+            write_allow: allow,
+            deny: Vec::new(),
+        }
+    }
+
+    /// Validates `path` against this scope for the given access direction.
+    ///
+    /// `path` is canonicalized (or, if it doesn't exist yet, lexically
+    /// normalized) before matching so a traversal like
+    /// `/home/alice/../../etc/shadow` can't textually satisfy an allow
+    /// pattern like `/home/alice/**` and sneak past the scope.
+    pub fn check(&self, path: &str, access: Access) -> Result<(), EngineError> {
+        let normalized = normalize_path(path);
+
+        if self
+            .deny
+            .iter()
+            .any(|pattern| glob_matches(pattern, &normalized))
+        {
+            return Err(EngineError::OutOfScope(format!(
+                "path '{path}' is denied by the active scope"
+            )));
+        }
+
+        let allow = match access {
+            Access::Read => &self.read_allow,
+            Access::Write => &self.write_allow,
+        };
+
+        if allow.iter().any(|pattern| glob_matches(pattern, &normalized)) {
+            Ok(())
+        } else {
+            Err(EngineError::OutOfScope(format!(
+                "path '{path}' is outside the allowed scope"
+            )))
+        }
+    }
+}
+
+/// Resolves `path` to its canonical form when it exists on disk; falls back
+/// to a purely lexical `.`/`..` normalization (e.g. for write targets that
+/// don't exist yet) so traversal segments can't be used to escape the scope
+/// either way.
+fn normalize_path(path: &str) -> String {
+    match std::fs::canonicalize(path) {
+        Ok(canonical) => {
+            let canonical = canonical.to_string_lossy().to_string();
+            // Windows canonicalizes to verbatim `\\?\C:\...` form, which no
+            // pattern is written in.
+            normalize_components(canonical.strip_prefix(r"\\?\").unwrap_or(&canonical))
+        }
+        Err(_) => normalize_components(path),
+    }
+}
+
+/// Resolves `.`/`..` components of `path` without touching the filesystem.
+/// Both `/` and `\` count as separators, and the result is joined with `/`.
+/// Glob wildcards (`*`, `**`) are ordinary components here and pass through
+/// untouched, so this is also safe to apply to scope patterns.
+fn normalize_components(path: &str) -> String {
+    let is_absolute = path.starts_with(['/', '\\']);
+    let mut stack: Vec<&str> = Vec::new();
+
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => match stack.last() {
+                Some(&last) if last != ".." => {
+                    stack.pop();
+                }
+                _ if !is_absolute => stack.push(".."),
+                _ => {}
+            },
+            other => stack.push(other),
+        }
+    }
+
+    let joined = stack.join("/");
+    if is_absolute {
+        format!("/{joined}")
+    } else {
+        joined
+    }
+}
+
+fn glob_matches(pattern: &str, path: &str) -> bool {
+    let normalized_pattern = normalize_components(pattern);
+    Pattern::new(&normalized_pattern)
+        .map(|glob| glob.matches(path))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn home_scope() -> Scope {
+        Scope {
+            read_allow: vec!["/home/alice/**".to_string()],
+            write_allow: vec!["/home/alice/**".to_string()],
+            deny: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rejects_traversal_outside_allowed_root() {
+        let scope = home_scope();
+        assert!(scope
+            .check("/home/alice/../../etc/shadow", Access::Read)
+            .is_err());
+    }
+
+    #[test]
+    fn allows_path_inside_allowed_root() {
+        let scope = home_scope();
+        assert!(scope
+            .check("/home/alice/configs/app.json", Access::Read)
+            .is_ok());
+    }
+}
+
+/// Returns the active scope so the UI can pre-filter file pickers.
+#[tauri::command]
+pub fn get_scope(scope: tauri::State<'_, Scope>) -> Scope {
+    scope.inner().clone()
+}